@@ -26,8 +26,8 @@ pub use self::{
 
 use crate::{configuration::DatasourceConnectorData, Configuration, Datasource, PreviewFeature};
 use chrono::{DateTime, FixedOffset};
-use diagnostics::{DatamodelError, Diagnostics, NativeTypeErrorFactory, Span};
-use enumflags2::BitFlags;
+use diagnostics::{DatamodelError, DatamodelWarning, Diagnostics, NativeTypeErrorFactory, Span};
+use enumflags2::{bitflags, BitFlags};
 use lsp_types::CompletionList;
 use parser_database::{
     ast::{self, SchemaPosition},
@@ -103,6 +103,22 @@ pub trait ValidatedConnector: Send + Sync {
         }
     }
 
+    /// This is used by the query engine schema builder. It is only called for
+    /// filters of range fields (`int4range`, `tsrange`, …).
+    ///
+    /// For a given filter input object type name returned by `scalar_filter_name`,
+    /// it should return the range operations to be made available in the Client
+    /// API. Like [`Self::string_filters`], implementations _must_ always associate
+    /// the same filters to the same input object type name, because the filter
+    /// types are cached by name.
+    ///
+    /// Defaults to the empty set, meaning the connector surfaces no range
+    /// filters. A connector with range native-type support overrides this to
+    /// return the variants it exposes for the given input object.
+    fn range_filters(&self, _input_object_name: &str) -> BitFlags<RangeFilter> {
+        BitFlags::empty()
+    }
+
     /// Debug/error representation of a native type.
     fn native_type_to_parts(&self, native_type: &NativeTypeInstance) -> (&'static str, Vec<String>);
 
@@ -345,6 +361,28 @@ pub trait Connector: Send + Sync + ValidatedConnector {
     /// Note: this is not used in any `query-engine`.
     fn validate_url(&self, url: &str) -> Result<(), String>;
 
+    /// The TLS modes this connector accepts for the `sslmode` datasource
+    /// argument, in order of increasing strictness. The connection URL itself is
+    /// not read. An empty set means the connector does not
+    /// model TLS through `sslmode` and any value is left to the driver.
+    ///
+    /// Used by `parse_datasource_properties` to reject connector-unsupported
+    /// modes with a `DatamodelError` during schema validation instead of failing
+    /// opaquely at connect time, and by `datasource_completions` to offer the
+    /// valid values.
+    /// Note: this is not used in any `query-engine`.
+    fn supported_ssl_modes(&self) -> &'static [SslMode] {
+        &[]
+    }
+
+    /// The practical upper bound on `connection_limit` for this connector, if
+    /// any. `parse_datasource_properties` warns when the `connection_limit`
+    /// datasource argument requests a limit above this value. `None` means the connector imposes no advisory ceiling.
+    /// Note: this is not used in any `query-engine`.
+    fn max_connection_limit(&self) -> Option<usize> {
+        None
+    }
+
     /// Note: this is not used in any `query-engine`.
     fn datamodel_completions(
         &self,
@@ -355,16 +393,188 @@ pub trait Connector: Send + Sync + ValidatedConnector {
     }
 
     /// Note: this is not used in any `query-engine`.
-    fn datasource_completions(&self, _config: &Configuration, _completion_list: &mut CompletionList) {}
+    fn datasource_completions(&self, _config: &Configuration, completion_list: &mut CompletionList) {
+        // Offer the TLS modes this connector accepts as completions for the
+        // `sslmode` argument value.
+        for mode in self.supported_ssl_modes() {
+            completion_list.items.push(lsp_types::CompletionItem {
+                label: mode.as_str().to_owned(),
+                kind: Some(lsp_types::CompletionItemKind::VALUE),
+                ..Default::default()
+            });
+        }
+    }
+
+    /// Validate the `sslmode` argument of a datasource against this connector's
+    /// [`Self::supported_ssl_modes`], pushing a [`DatamodelError`] at the
+    /// offending [`Span`] for an unsupported value. An empty supported set means
+    /// the connector does not model TLS through `sslmode`, so any value is left
+    /// to the driver and nothing is validated.
+    ///
+    /// Exposed as its own method (rather than inlined in the default
+    /// `parse_datasource_properties`) so connectors that override
+    /// `parse_datasource_properties` can still run the shared TLS validation by
+    /// calling `self.validate_ssl_configuration(args, diagnostics)`.
+    /// Note: this is not used in any `query-engine`.
+    fn validate_ssl_configuration(
+        &self,
+        args: &HashMap<&str, (Span, &ast::Expression)>,
+        diagnostics: &mut Diagnostics,
+    ) {
+        let supported = self.supported_ssl_modes();
+        if supported.is_empty() {
+            return;
+        }
+
+        let Some((span, expr)) = args.get("sslmode") else {
+            return;
+        };
+        let Some((value, _)) = expr.as_string_value() else {
+            return;
+        };
+
+        let valid = value.parse::<SslMode>().map(|mode| supported.contains(&mode)).unwrap_or(false);
+        if !valid {
+            let allowed = supported.iter().map(|m| m.as_str()).collect::<Vec<_>>().join(", ");
+            diagnostics.push_error(DatamodelError::new_validation_error(
+                &format!("Invalid `sslmode` value `{value}` for this connector. Supported values: {allowed}."),
+                *span,
+            ));
+        }
+    }
 
     /// Note: this is not used in any `query-engine`.
     fn parse_datasource_properties(
         &self,
-        _args: &mut HashMap<&str, (Span, &ast::Expression)>,
-        _diagnostics: &mut Diagnostics,
+        args: &mut HashMap<&str, (Span, &ast::Expression)>,
+        diagnostics: &mut Diagnostics,
     ) -> DatasourceConnectorData {
+        self.validate_ssl_configuration(args, diagnostics);
+        self.validate_pool_configuration(args, diagnostics);
+
         Default::default()
     }
+
+    /// Validate the standard connection-pool tuning arguments (`connection_limit`,
+    /// `pool_timeout`, `connect_timeout`, `socket_timeout`) given as datasource
+    /// arguments; query parameters inside the connection URL are not read, and
+    /// the values are only validated, not returned. A `connection_limit` of `0` or a negative value is
+    /// rejected, a limit above this connector's advisory
+    /// [`Self::max_connection_limit`] warns, and negative timeouts are rejected —
+    /// all against the offending argument [`Span`].
+    ///
+    /// Like [`Self::validate_ssl_configuration`], exposed as its own method so
+    /// connectors overriding `parse_datasource_properties` can still run the
+    /// shared pool validation.
+    /// Note: this is not used in any `query-engine`.
+    fn validate_pool_configuration(
+        &self,
+        args: &HashMap<&str, (Span, &ast::Expression)>,
+        diagnostics: &mut Diagnostics,
+    ) {
+        let max_connection_limit = self.max_connection_limit();
+
+        if let Some((span, value)) = args.get("connection_limit").and_then(|(s, e)| numeric_arg(e).map(|v| (*s, v))) {
+            if value == 0 {
+                diagnostics.push_error(DatamodelError::new_validation_error(
+                    "The `connection_limit` must be greater than 0.",
+                    span,
+                ));
+            } else if value < 0 {
+                diagnostics.push_error(DatamodelError::new_validation_error(
+                    "The `connection_limit` cannot be negative.",
+                    span,
+                ));
+            } else if let Some(max) = max_connection_limit {
+                if value as usize > max {
+                    diagnostics.push_warning(DatamodelWarning::new(
+                        format!("The `connection_limit` of {value} exceeds this connector's practical maximum of {max}."),
+                        span,
+                    ));
+                }
+            }
+        }
+
+        for arg in ["pool_timeout", "connect_timeout", "socket_timeout"] {
+            if let Some((span, value)) = args.get(arg).and_then(|(s, e)| numeric_arg(e).map(|v| (*s, v))) {
+                if value < 0 {
+                    diagnostics.push_error(DatamodelError::new_validation_error(
+                        &format!("The `{arg}` cannot be negative."),
+                        span,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Parse a datasource argument expression as an integer, returning `None` when
+/// it is not a numeric literal (e.g. an env function call, resolved later).
+fn numeric_arg(expr: &ast::Expression) -> Option<i64> {
+    expr.as_numeric_value().and_then(|(value, _)| value.parse().ok())
+}
+
+/// The range operators a connector exposes on a range filter input object,
+/// returned by [`ValidatedConnector::range_filters`]. Mirrors `StringFilter`:
+/// the query engine builds the nested filter input for a range native type
+/// (`int4range`, `tsrange`, …) from the set of variants the connector enables.
+#[bitflags]
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeFilter {
+    /// Equality of the whole range value.
+    Equals,
+    /// `@>` — the range contains the given element or range.
+    Contains,
+    /// `<@` — the range is contained by the given range.
+    ContainedBy,
+    /// `&&` — the ranges overlap (share at least one point).
+    Overlaps,
+    /// `-|-` — the ranges are adjacent.
+    AdjacentTo,
+    /// `<<` — the range is strictly left of the given range.
+    StrictlyLeftOf,
+    /// `>>` — the range is strictly right of the given range.
+    StrictlyRightOf,
+}
+
+/// A parsed, connector-validated TLS mode taken from the `sslmode` datasource
+/// argument. Only the canonical libpq spellings are parsed; any other spelling
+/// is rejected as unknown.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SslMode {
+    Disable,
+    Prefer,
+    Require,
+    VerifyCa,
+    VerifyFull,
+}
+
+impl SslMode {
+    /// The value as it appears in a connection URL.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SslMode::Disable => "disable",
+            SslMode::Prefer => "prefer",
+            SslMode::Require => "require",
+            SslMode::VerifyCa => "verify-ca",
+            SslMode::VerifyFull => "verify-full",
+        }
+    }
+}
+
+impl FromStr for SslMode {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "disable" => Ok(Self::Disable),
+            "prefer" => Ok(Self::Prefer),
+            "require" => Ok(Self::Require),
+            "verify-ca" => Ok(Self::VerifyCa),
+            "verify-full" => Ok(Self::VerifyFull),
+            _ => Err(format!("Unknown sslmode: {}", s)),
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -380,6 +590,13 @@ pub enum Flavour {
 impl FromStr for Flavour {
     type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Consult the registry first: a registered connector claiming this
+        // provider name shadows the built-in flavours (see [`registry`]), so a
+        // third-party backend can define its own flavour without forking PSL.
+        if let Some(connector) = registry::connector_for_provider(s) {
+            return Ok(connector.flavour());
+        }
+
         match s.to_lowercase().as_str() {
             "mysql" => Ok(Self::Mysql),
             "postgres" => Ok(Self::Postgres),
@@ -399,6 +616,68 @@ pub enum ConstraintType {
     Default,
 }
 
+/// A process-wide registry of [`Connector`] implementations, so a third-party
+/// crate can add support for a new database (or a variant flavour) without
+/// forking PSL. The registry offers [`connector_for_provider`]
+/// (provider→`Connector` lookup) and [`parse_native_type_for_provider`]
+/// (native-type resolution). [`Flavour::from_str`] consults it before the
+/// built-in flavours; the parser's built-in provider dispatch and native-type
+/// resolution do not call it.
+///
+/// Precedence within these lookups: a registered connector whose
+/// [`ValidatedConnector::is_provider`] accepts a name is returned, and the most
+/// recently registered match wins. `None` is returned when no registered
+/// connector claims the name.
+pub mod registry {
+    use super::{Connector, NativeTypeInstance};
+    use diagnostics::{Diagnostics, Span};
+    use std::sync::{Arc, OnceLock, RwLock};
+
+    type Registry = RwLock<Vec<Arc<dyn Connector>>>;
+
+    fn registry() -> &'static Registry {
+        static REGISTRY: OnceLock<Registry> = OnceLock::new();
+        REGISTRY.get_or_init(|| RwLock::new(Vec::new()))
+    }
+
+    /// Register a connector implementation. It takes precedence over built-in
+    /// providers and over connectors registered earlier for the same name.
+    pub fn register_connector(connector: Arc<dyn Connector>) {
+        registry().write().unwrap().push(connector);
+    }
+
+    /// Look up a registered connector that accepts `provider`, honoring the
+    /// documented precedence (most recently registered match wins). Returns
+    /// `None` when no registered connector claims the name, in which case the
+    /// caller should fall back to the built-in providers.
+    pub fn connector_for_provider(provider: &str) -> Option<Arc<dyn Connector>> {
+        registry()
+            .read()
+            .unwrap()
+            .iter()
+            .rev()
+            .find(|connector| connector.is_provider(provider))
+            .cloned()
+    }
+
+    /// Resolve a native type through a registered connector, if one claims
+    /// `provider`.
+    ///
+    /// The outer `Option` encodes the precedence: `None` means no registered
+    /// connector claims the provider name, while `Some(inner)` means a registered connector
+    /// handled it and `inner` is its verdict (`None` there is the connector's
+    /// own "unknown native type", already reported through `diagnostics`).
+    pub fn parse_native_type_for_provider(
+        provider: &str,
+        name: &str,
+        args: &[String],
+        span: Span,
+        diagnostics: &mut Diagnostics,
+    ) -> Option<Option<NativeTypeInstance>> {
+        connector_for_provider(provider).map(|connector| connector.parse_native_type(name, args, span, diagnostics))
+    }
+}
+
 /// A scope where a constraint name must be unique.
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
 pub enum ConstraintScope {
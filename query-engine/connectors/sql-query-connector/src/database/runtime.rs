@@ -6,154 +6,632 @@ use quaint::{
     prelude::{Query, Queryable, TransactionCapable},
     Value,
 };
+use rand::Rng;
+use std::{borrow::Cow, collections::HashMap, sync::Arc, time::Duration};
 use tracing::{info_span, Instrument};
 
 #[cfg(feature = "js-drivers")]
 type QueryableRef = std::sync::Arc<dyn Queryable>;
 
+/// Which driver backs a given [`RuntimeConnection`]. Carried on [`QueryContext`]
+/// so interceptors can branch on the dispatch path without downcasting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionKind {
+    /// The native Quaint connection pool.
+    Rust,
+    /// A JavaScript driver adapter.
+    Js,
+}
+
+/// The mutable context handed to the [`QueryInterceptor`] chain around every
+/// statement-dispatching [`Queryable`] method. `before` hooks may rewrite `sql`/`params`, abort the
+/// dispatch altogether, or stash values in `user_data`; `after` hooks observe
+/// the same context plus the dispatch error, if any.
+pub struct QueryContext<'a> {
+    /// The statement being dispatched. For the prepared-statement entry points
+    /// (`query`/`execute`) the SQL text is opaque, so this is left empty.
+    pub sql: Cow<'a, str>,
+    /// The bound parameters, when dispatched through a `*_raw` entry point. Owned
+    /// through a [`Cow`] so `before` hooks can redact them in place before they
+    /// reach the driver and the spans.
+    pub params: Cow<'a, [Value<'a>]>,
+    /// The driver backing the connection.
+    pub kind: ConnectionKind,
+    /// A free-form bag interceptors can use to thread state between `before` and
+    /// `after`, e.g. a timer or tenant id.
+    pub user_data: HashMap<String, String>,
+    /// Set by a `before` hook that short-circuits the dispatch: the statement is
+    /// never sent and the call fails with this message. See [`Interception`].
+    abort: Option<String>,
+}
+
+impl<'a> QueryContext<'a> {
+    fn new(sql: impl Into<Cow<'a, str>>, params: impl Into<Cow<'a, [Value<'a>]>>, kind: ConnectionKind) -> Self {
+        QueryContext {
+            sql: sql.into(),
+            params: params.into(),
+            kind,
+            user_data: HashMap::new(),
+            abort: None,
+        }
+    }
+}
+
+/// The decision a `before` hook returns: either let the (possibly mutated)
+/// statement through, or short-circuit the whole call with an error, so an
+/// interceptor can enforce a tenant boundary or reject a statement without it
+/// ever reaching the database.
+pub enum Interception {
+    /// Dispatch the statement using the current [`QueryContext`].
+    Proceed,
+    /// Skip dispatch and fail the call with this error message.
+    Abort(String),
+}
+
+/// A cross-cutting hook wrapped around the statement-dispatching [`Queryable`]
+/// methods of a [`RuntimeConnection`] (`query*`, `execute*` and `raw_cmd`);
+/// `version`, `is_healthy` and the isolation-level methods do not run the
+/// chain. Interceptors run in registration order before the
+/// statement is dispatched and in the same order afterwards, enabling metrics,
+/// audit logging, query rewriting, tenant enforcement or parameter redaction
+/// without patching each call site.
+pub trait QueryInterceptor: Send + Sync {
+    /// Runs before the statement is dispatched. May mutate the context — e.g.
+    /// rewrite the SQL or redact parameters before they reach the driver and the
+    /// spans — or return [`Interception::Abort`] to short-circuit the call. Once
+    /// one hook aborts, the remaining hooks are skipped.
+    fn before(&self, _ctx: &mut QueryContext<'_>) -> Interception {
+        Interception::Proceed
+    }
+
+    /// Runs after the statement has been dispatched, with the error description
+    /// when the dispatch failed.
+    fn after(&self, _ctx: &QueryContext<'_>, _error: Option<&str>) {}
+}
+
+/// Build the error returned when a `before` hook aborts the dispatch.
+fn intercepted_error(message: String) -> quaint::error::Error {
+    quaint::error::Error::builder(quaint::error::ErrorKind::QueryError(message.into())).build()
+}
+
+/// A shared, cheaply-cloneable interceptor chain.
+pub type Interceptors = Arc<[Box<dyn QueryInterceptor>]>;
+
+/// Controls how transient failures are retried. Idempotent operations (the read
+/// paths: `query`, `query_raw`, `version`) are retried up to `max_retries` times
+/// with exponential backoff; potentially non-idempotent operations are only
+/// retried when the failure provably occurred before the statement reached the
+/// server (e.g. a pool checkout timeout).
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// The maximum number of retries after the initial attempt.
+    pub max_retries: u32,
+    /// The base delay, doubled on every attempt.
+    pub base_delay: Duration,
+    /// The ceiling on a single backoff delay.
+    pub max_delay: Duration,
+    /// Whether to add random jitter to each delay to avoid thundering herds.
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 0,
+            base_delay: Duration::from_millis(50),
+            max_delay: Duration::from_secs(5),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The backoff delay before the given (zero-based) attempt:
+    /// `min(max_delay, base_delay * 2^attempt)` plus optional jitter.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt);
+        let mut delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+
+        // Jitter is added on top of the exponential floor (up to the computed
+        // delay again, capped at `max_delay`) rather than replacing it, so every
+        // attempt still waits at least the computed `delay`.
+        if self.jitter && !delay.is_zero() {
+            let jitter = rand::thread_rng().gen_range(0..=delay.as_millis() as u64);
+            delay = delay
+                .saturating_add(Duration::from_millis(jitter))
+                .min(self.max_delay);
+        }
+
+        delay
+    }
+}
+
+/// Shared configuration inherited by every connection checked out of a pool.
+#[derive(Clone, Default)]
+struct ConnectionConfig {
+    interceptors: Interceptors,
+    retry: RetryPolicy,
+    /// A semaphore bounding concurrent checkouts to `connection_limit`. `None`
+    /// leaves acquisition unbounded.
+    limiter: Option<Arc<tokio::sync::Semaphore>>,
+    /// How long a caller waits to acquire a permit before a checkout fails with
+    /// a `SqlError::ConnectionError` instead of blocking forever.
+    pool_timeout: Option<Duration>,
+    /// Held for the lifetime of a checked-out connection so the permit is
+    /// released back to `limiter` on drop.
+    _permit: Option<Arc<tokio::sync::OwnedSemaphorePermit>>,
+}
+
+/// Whether a dispatch failure is transient and therefore worth retrying:
+/// connection resets, broken pipes, pool checkout timeouts and
+/// deadlock/serialization errors.
+fn is_transient(error: &quaint::error::Error) -> bool {
+    use quaint::error::ErrorKind::*;
+
+    matches!(
+        error.kind(),
+        ConnectionClosed | PoolTimeout { .. } | SocketTimeout | TransactionWriteConflict
+    ) || error.is_closed()
+}
+
 pub enum RuntimePool {
-    Rust(Quaint),
+    Rust(Quaint, ConnectionConfig),
 
     #[cfg(feature = "js-drivers")]
-    Js(QueryableRef),
+    Js(QueryableRef, ConnectionConfig),
 }
 
 impl RuntimePool {
+    /// Wrap a native Quaint pool with the default configuration.
+    pub fn rust(pool: Quaint) -> Self {
+        Self::Rust(pool, ConnectionConfig::default())
+    }
+
+    /// Wrap a JS driver adapter with the default configuration.
+    #[cfg(feature = "js-drivers")]
+    pub fn js(queryable: QueryableRef) -> Self {
+        Self::Js(queryable, ConnectionConfig::default())
+    }
+
+    /// The pool-wide configuration, mutably. Used by the `with_*` setters to
+    /// install the interceptor chain, retry policy and acquisition limits that
+    /// every connection checked out of this pool inherits.
+    fn config_mut(&mut self) -> &mut ConnectionConfig {
+        match self {
+            Self::Rust(_, config) => config,
+
+            #[cfg(feature = "js-drivers")]
+            Self::Js(_, config) => config,
+        }
+    }
+
+    /// Install the interceptor chain run around the statement-dispatching
+    /// [`Queryable`] methods of the connections checked out of this pool.
+    pub fn with_interceptors(mut self, interceptors: Interceptors) -> Self {
+        self.config_mut().interceptors = interceptors;
+        self
+    }
+
+    /// Set the [`RetryPolicy`] stored on the pool and inherited by every
+    /// connection. Without this the pool keeps the default policy, which does
+    /// not retry.
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.config_mut().retry = retry;
+        self
+    }
+
+    /// Bound acquisition to at most `connection_limit` concurrent checkouts,
+    /// with callers that can't acquire within `pool_timeout` failing with a
+    /// `SqlError::ConnectionError` instead of blocking forever. A
+    /// `connection_limit` of `None` leaves acquisition unbounded.
+    pub fn with_connection_limit(mut self, connection_limit: Option<usize>, pool_timeout: Option<Duration>) -> Self {
+        let config = self.config_mut();
+        config.limiter = connection_limit.map(|limit| Arc::new(tokio::sync::Semaphore::new(limit)));
+        config.pool_timeout = pool_timeout;
+        self
+    }
+
     pub fn is_nodejs(&self) -> bool {
         match self {
-            Self::Rust(_) => false,
+            Self::Rust(_, _) => false,
 
             #[cfg(feature = "js-drivers")]
-            Self::Js(_) => true,
+            Self::Js(_, _) => true,
         }
     }
 
-    /// Reserve a connection from the pool
-    pub async fn check_out(&self) -> crate::Result<RuntimeConnection> {
+    /// The configuration every connection checked out of this pool inherits.
+    fn config(&self) -> ConnectionConfig {
         match self {
-            Self::Rust(pool) => {
-                let conn: PooledConnection = pool.check_out().await.map_err(SqlError::from)?;
-                Ok(RuntimeConnection::Rust(conn))
+            Self::Rust(_, config) => config.clone(),
+
+            #[cfg(feature = "js-drivers")]
+            Self::Js(_, config) => config.clone(),
+        }
+    }
+
+    /// Reserve a connection from the pool.
+    ///
+    /// A checkout failure provably occurs before any statement reaches the
+    /// server, so it is safe to retry even for non-idempotent callers.
+    pub async fn check_out(&self) -> crate::Result<RuntimeConnection> {
+        let mut config = self.config();
+
+        // Bounded, semaphore-gated acquisition: at most `connection_limit`
+        // concurrent checkouts, with callers that can't acquire within
+        // `pool_timeout` returning a connection error rather than blocking forever.
+        if let Some(limiter) = config.limiter.clone() {
+            let acquire = limiter.acquire_owned();
+            let permit = match config.pool_timeout {
+                Some(timeout) => tokio::time::timeout(timeout, acquire)
+                    .await
+                    .map_err(|_| SqlError::ConnectionError("Timed out acquiring a connection from the pool".into()))?
+                    .expect("the pool semaphore is never closed"),
+                None => acquire.await.expect("the pool semaphore is never closed"),
+            };
+            config._permit = Some(Arc::new(permit));
+        }
+
+        let retry = &config.retry;
+        let mut attempt = 0;
+        let conn = loop {
+            let result = match self {
+                Self::Rust(pool, _) => pool.check_out().await.map(Some).map_err(SqlError::from),
+
+                #[cfg(feature = "js-drivers")]
+                Self::Js(_, _) => Ok(None),
+            };
+
+            match result {
+                Ok(conn) => break conn,
+                // A checkout failure happens before any statement reaches the
+                // server, so retrying it is always safe.
+                Err(_) if attempt < retry.max_retries => {
+                    let span = info_span!("runtime_connection::retry", attempt = attempt + 1);
+                    tokio::time::sleep(retry.backoff(attempt)).instrument(span).await;
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
             }
+        };
+
+        match self {
+            Self::Rust(_, _) => Ok(RuntimeConnection::Rust(conn.unwrap(), config)),
+
             #[cfg(feature = "js-drivers")]
-            Self::Js(queryable) => Ok(RuntimeConnection::Js(queryable.clone())),
+            Self::Js(queryable, _) => Ok(RuntimeConnection::Js(queryable.clone(), config)),
         }
     }
 }
 
 pub enum RuntimeConnection {
-    Rust(PooledConnection),
+    Rust(PooledConnection, ConnectionConfig),
 
     #[cfg(feature = "js-drivers")]
-    Js(QueryableRef),
+    Js(QueryableRef, ConnectionConfig),
 }
 
-#[async_trait]
-impl Queryable for RuntimeConnection {
-    async fn query(&self, q: Query<'_>) -> quaint::Result<quaint::prelude::ResultSet> {
+impl RuntimeConnection {
+    fn kind(&self) -> ConnectionKind {
         match self {
-            Self::Rust(conn) => conn.query(q).await,
+            Self::Rust(_, _) => ConnectionKind::Rust,
 
             #[cfg(feature = "js-drivers")]
-            Self::Js(conn) => {
-                let span = info_span!("runtime_connection::js::query", user_facing = true);
-                conn.query(q).instrument(span).await
-            }
+            Self::Js(_, _) => ConnectionKind::Js,
         }
     }
 
-    async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> quaint::Result<quaint::prelude::ResultSet> {
+    fn config(&self) -> &ConnectionConfig {
         match self {
-            Self::Rust(conn) => conn.query_raw(sql, params).await,
+            Self::Rust(_, config) => config,
 
             #[cfg(feature = "js-drivers")]
-            Self::Js(conn) => {
-                let span = info_span!("runtime_connection::js::query_raw", user_facing = true);
-                conn.query_raw(sql, params).instrument(span).await
+            Self::Js(_, config) => config,
+        }
+    }
+
+    fn interceptors(&self) -> &Interceptors {
+        &self.config().interceptors
+    }
+
+    /// Retry an idempotent operation on transient failures following the pool's
+    /// [`RetryPolicy`]. The backoff wait before each retry runs inside a child
+    /// span annotated with the attempt number so it surfaces in the exported
+    /// telemetry.
+    async fn with_retry<T, F, Fut>(&self, mut op: F) -> quaint::Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = quaint::Result<T>>,
+    {
+        let retry = &self.config().retry;
+
+        let mut attempt = 0;
+        loop {
+            let result = op().await;
+
+            match result {
+                Err(ref err) if attempt < retry.max_retries && is_transient(err) => {
+                    let span = info_span!("runtime_connection::retry", attempt = attempt + 1);
+                    async {
+                        tokio::time::sleep(retry.backoff(attempt)).await;
+                    }
+                    .instrument(span)
+                    .await;
+                    attempt += 1;
+                }
+                _ => return result,
             }
         }
     }
 
-    async fn query_raw_typed(&self, sql: &str, params: &[Value<'_>]) -> quaint::Result<quaint::prelude::ResultSet> {
-        match self {
-            Self::Rust(conn) => conn.query_raw_typed(sql, params).await,
+    /// Run the `before` chain over a freshly built context and hand it back so
+    /// the (possibly mutated) values can drive the dispatch. The first hook that
+    /// returns [`Interception::Abort`] records its message on the context and
+    /// stops the chain; callers check [`QueryContext::abort`] before dispatching.
+    fn run_before<'a>(&self, mut ctx: QueryContext<'a>) -> QueryContext<'a> {
+        for interceptor in self.interceptors().iter() {
+            if let Interception::Abort(message) = interceptor.before(&mut ctx) {
+                ctx.abort = Some(message);
+                break;
+            }
+        }
+        ctx
+    }
 
-            #[cfg(feature = "js-drivers")]
-            Self::Js(conn) => {
-                let span = info_span!("runtime_connection::js::query_raw_typed", user_facing = true);
-                conn.query_raw_typed(sql, params).instrument(span).await
+    /// If a `before` hook aborted the dispatch, build the short-circuit error,
+    /// run the `after` chain over it and hand it back for the caller to return.
+    /// Returns `None` when no hook aborted, in which case dispatch proceeds.
+    fn aborted<T>(&self, ctx: &QueryContext<'_>) -> Option<quaint::Result<T>> {
+        ctx.abort.as_ref().map(|message| {
+            let result: quaint::Result<T> = Err(intercepted_error(message.clone()));
+            self.run_after(ctx, &result);
+            result
+        })
+    }
+
+    /// Run the `after` chain with the outcome of the dispatch.
+    fn run_after<T>(&self, ctx: &QueryContext<'_>, result: &quaint::Result<T>) {
+        let error = result.as_ref().err().map(|e| e.to_string());
+        for interceptor in self.interceptors().iter() {
+            interceptor.after(ctx, error.as_deref());
+        }
+    }
+}
+
+impl RuntimeConnection {
+    /// Dispatch multiple write statements as a single atomic unit instead of
+    /// `N` round-trips. Both the Rust and JS paths run the statements inside one
+    /// transaction obtained through [`TransactionCapable::start_transaction`]
+    /// (implemented for `RuntimeConnection` below): every statement is `execute`d
+    /// in order, the affected-row counts are collected and, on success, the
+    /// transaction is committed. If any statement fails the transaction is rolled
+    /// back (by dropping it without committing) and the error is returned, so the
+    /// batch is all-or-nothing.
+    ///
+    /// The JS path shares this transactional implementation rather than a
+    /// driver-native multi-statement call: the `QueryableRef` (`Arc<dyn
+    /// Queryable>`) exposes no batch entry point, so there is nothing to forward
+    /// to, and the transaction gives the same all-or-nothing guarantee across
+    /// both backends.
+    ///
+    /// This meaningfully cuts latency for bulk `createMany`/`updateMany`
+    /// workloads.
+    pub async fn batch(&self, statements: Vec<Query<'_>>) -> quaint::Result<Vec<u64>> {
+        let span = info_span!("runtime_connection::batch", user_facing = true);
+
+        async move {
+            let tx = self.start_transaction(None).await?;
+
+            let mut affected = Vec::with_capacity(statements.len());
+            for statement in statements {
+                affected.push(tx.execute(statement).await?);
             }
+
+            tx.commit().await?;
+            Ok(affected)
+        }
+        .instrument(span)
+        .await
+    }
+}
+
+#[async_trait]
+impl Queryable for RuntimeConnection {
+    async fn query(&self, q: Query<'_>) -> quaint::Result<quaint::prelude::ResultSet> {
+        let ctx = self.run_before(QueryContext::new("", &[], self.kind()));
+        if let Some(result) = self.aborted(&ctx) {
+            return result;
+        }
+
+        // `query` is idempotent, so it is safe to retry on transient failures.
+        let result = self
+            .with_retry(|| {
+                let q = q.clone();
+                async move {
+                    match self {
+                        Self::Rust(conn, _) => conn.query(q).await,
+
+                        #[cfg(feature = "js-drivers")]
+                        Self::Js(conn, _) => {
+                            let span = info_span!("runtime_connection::js::query", user_facing = true);
+                            conn.query(q).instrument(span).await
+                        }
+                    }
+                }
+            })
+            .await;
+
+        self.run_after(&ctx, &result);
+        result
+    }
+
+    async fn query_raw(&self, sql: &str, params: &[Value<'_>]) -> quaint::Result<quaint::prelude::ResultSet> {
+        let ctx = self.run_before(QueryContext::new(sql, params, self.kind()));
+        if let Some(result) = self.aborted(&ctx) {
+            return result;
+        }
+
+        // The dispatch is driven from the (possibly rewritten) context rather
+        // than the original arguments so interceptors can rewrite the SQL or
+        // redact the parameters before they reach the driver.
+        let (sql, params) = (ctx.sql.as_ref(), ctx.params.as_ref());
+
+        // `query_raw` is idempotent, so it is safe to retry on transient failures.
+        let result = self
+            .with_retry(|| async {
+                match self {
+                    Self::Rust(conn, _) => conn.query_raw(sql, params).await,
+
+                    #[cfg(feature = "js-drivers")]
+                    Self::Js(conn, _) => {
+                        let span = info_span!("runtime_connection::js::query_raw", user_facing = true);
+                        conn.query_raw(sql, params).instrument(span).await
+                    }
+                }
+            })
+            .await;
+
+        self.run_after(&ctx, &result);
+        result
+    }
+
+    async fn query_raw_typed(&self, sql: &str, params: &[Value<'_>]) -> quaint::Result<quaint::prelude::ResultSet> {
+        let ctx = self.run_before(QueryContext::new(sql, params, self.kind()));
+        if let Some(result) = self.aborted(&ctx) {
+            return result;
         }
+
+        let (sql, params) = (ctx.sql.as_ref(), ctx.params.as_ref());
+
+        // `query_raw_typed` is idempotent, so it is safe to retry on transient failures.
+        let result = self
+            .with_retry(|| async {
+                match self {
+                    Self::Rust(conn, _) => conn.query_raw_typed(sql, params).await,
+
+                    #[cfg(feature = "js-drivers")]
+                    Self::Js(conn, _) => {
+                        let span = info_span!("runtime_connection::js::query_raw_typed", user_facing = true);
+                        conn.query_raw_typed(sql, params).instrument(span).await
+                    }
+                }
+            })
+            .await;
+
+        self.run_after(&ctx, &result);
+        result
     }
 
     async fn execute(&self, q: Query<'_>) -> quaint::Result<u64> {
-        match self {
-            Self::Rust(conn) => conn.execute(q).await,
+        let ctx = self.run_before(QueryContext::new("", &[], self.kind()));
+        if let Some(result) = self.aborted(&ctx) {
+            return result;
+        }
+
+        let result = match self {
+            Self::Rust(conn, _) => conn.execute(q).await,
 
             #[cfg(feature = "js-drivers")]
-            Self::Js(conn) => {
+            Self::Js(conn, _) => {
                 let span = info_span!("runtime_connection::js::execute", user_facing = true);
                 conn.execute(q).instrument(span).await
             }
-        }
+        };
+
+        self.run_after(&ctx, &result);
+        result
     }
 
     async fn execute_raw(&self, sql: &str, params: &[Value<'_>]) -> quaint::Result<u64> {
-        match self {
-            Self::Rust(conn) => conn.execute_raw(sql, params).await,
+        let ctx = self.run_before(QueryContext::new(sql, params, self.kind()));
+        if let Some(result) = self.aborted(&ctx) {
+            return result;
+        }
+
+        let (sql, params) = (ctx.sql.as_ref(), ctx.params.as_ref());
+
+        let result = match self {
+            Self::Rust(conn, _) => conn.execute_raw(sql, params).await,
 
             #[cfg(feature = "js-drivers")]
-            Self::Js(conn) => {
+            Self::Js(conn, _) => {
                 let span = info_span!("runtime_connection::js::execute_raw", user_facing = true);
                 conn.execute_raw(sql, params).instrument(span).await
             }
-        }
+        };
+
+        self.run_after(&ctx, &result);
+        result
     }
 
     async fn execute_raw_typed(&self, sql: &str, params: &[Value<'_>]) -> quaint::Result<u64> {
-        match self {
-            Self::Rust(conn) => conn.execute_raw_typed(sql, params).await,
+        let ctx = self.run_before(QueryContext::new(sql, params, self.kind()));
+        if let Some(result) = self.aborted(&ctx) {
+            return result;
+        }
+
+        let (sql, params) = (ctx.sql.as_ref(), ctx.params.as_ref());
+
+        let result = match self {
+            Self::Rust(conn, _) => conn.execute_raw_typed(sql, params).await,
 
             #[cfg(feature = "js-drivers")]
-            Self::Js(conn) => {
+            Self::Js(conn, _) => {
                 let span = info_span!("runtime_connection::js::execute_raw_typed", user_facing = true);
                 conn.execute_raw_typed(sql, params).instrument(span).await
             }
-        }
+        };
+
+        self.run_after(&ctx, &result);
+        result
     }
 
     /// Run a command in the database, for queries that can't be run using
     /// prepared statements.
     async fn raw_cmd(&self, cmd: &str) -> quaint::Result<()> {
-        match self {
-            Self::Rust(conn) => conn.raw_cmd(cmd).await,
+        let ctx = self.run_before(QueryContext::new(cmd.to_owned(), &[], self.kind()));
+        if let Some(result) = self.aborted(&ctx) {
+            return result;
+        }
+
+        let cmd = ctx.sql.as_ref();
+
+        let result = match self {
+            Self::Rust(conn, _) => conn.raw_cmd(cmd).await,
 
             #[cfg(feature = "js-drivers")]
-            Self::Js(conn) => {
+            Self::Js(conn, _) => {
                 let span = info_span!("runtime_connection::js::raw_cmd", user_facing = true);
                 conn.raw_cmd(cmd).instrument(span).await
             }
-        }
+        };
+
+        self.run_after(&ctx, &result);
+        result
     }
 
     async fn version(&self) -> quaint::Result<Option<String>> {
-        match self {
-            Self::Rust(conn) => conn.version().await,
+        // `version` is idempotent, so it is safe to retry on transient failures.
+        self.with_retry(|| async {
+            match self {
+                Self::Rust(conn, _) => conn.version().await,
 
-            #[cfg(feature = "js-drivers")]
-            Self::Js(conn) => {
-                let span = info_span!("runtime_connection::js::version", user_facing = true);
-                conn.version().instrument(span).await
+                #[cfg(feature = "js-drivers")]
+                Self::Js(conn, _) => {
+                    let span = info_span!("runtime_connection::js::version", user_facing = true);
+                    conn.version().instrument(span).await
+                }
             }
-        }
+        })
+        .await
     }
 
     fn is_healthy(&self) -> bool {
         match self {
-            Self::Rust(conn) => conn.is_healthy(),
+            Self::Rust(conn, _) => conn.is_healthy(),
 
             #[cfg(feature = "js-drivers")]
-            Self::Js(conn) => {
+            Self::Js(conn, _) => {
                 let span = info_span!("runtime_connection::js::is_healthy", user_facing = true);
                 span.in_scope(|| conn.is_healthy())
             }
@@ -164,10 +642,10 @@ impl Queryable for RuntimeConnection {
     /// Implementers have to make sure that the passed isolation level is valid for the underlying database.
     async fn set_tx_isolation_level(&self, isolation_level: IsolationLevel) -> quaint::Result<()> {
         match self {
-            Self::Rust(conn) => conn.set_tx_isolation_level(isolation_level).await,
+            Self::Rust(conn, _) => conn.set_tx_isolation_level(isolation_level).await,
 
             #[cfg(feature = "js-drivers")]
-            Self::Js(conn) => {
+            Self::Js(conn, _) => {
                 let span = info_span!("runtime_connection::js::set_tx_isolation_level", user_facing = true);
                 conn.set_tx_isolation_level(isolation_level).instrument(span).await
             }
@@ -177,10 +655,10 @@ impl Queryable for RuntimeConnection {
     /// Signals if the isolation level SET needs to happen before or after the tx BEGIN.
     fn requires_isolation_first(&self) -> bool {
         match self {
-            Self::Rust(conn) => conn.requires_isolation_first(),
+            Self::Rust(conn, _) => conn.requires_isolation_first(),
 
             #[cfg(feature = "js-drivers")]
-            Self::Js(conn) => conn.requires_isolation_first(),
+            Self::Js(conn, _) => conn.requires_isolation_first(),
         }
     }
 }
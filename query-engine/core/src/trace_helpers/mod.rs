@@ -5,10 +5,21 @@ use std::{collections::HashMap, time::SystemTime};
 use tracing::Span;
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
-const ACCEPT_ATTRIBUTES: &[&str] = &["db.statement", "itx_id", "db.type"];
+use opentelemetry::trace::Status;
 
+/// The attributes exported by default when no explicit allow-list is passed.
+pub const DEFAULT_ACCEPT_ATTRIBUTES: &[&str] = &["db.statement", "itx_id", "db.type"];
+
+/// Serialize spans to the telemetry JSON using the [`DEFAULT_ACCEPT_ATTRIBUTES`]
+/// allow-list. Back-compat entry point for the request handlers that don't pass
+/// a custom allow-list; see [`spans_to_json_with_attributes`] for the
+/// configurable variant.
 pub fn spans_to_json(spans: &[SpanData]) -> String {
-    let json_spans: Vec<Value> = spans.iter().map(span_to_json).collect();
+    spans_to_json_with_attributes(spans, DEFAULT_ACCEPT_ATTRIBUTES)
+}
+
+pub fn spans_to_json_with_attributes(spans: &[SpanData], accept_attributes: &[&str]) -> String {
+    let json_spans: Vec<Value> = spans.iter().map(|span| span_to_json(span, accept_attributes)).collect();
     let span_result = json!({
         "span": true,
         "spans": json_spans
@@ -20,12 +31,12 @@ pub fn spans_to_json(spans: &[SpanData]) -> String {
     }
 }
 
-fn span_to_json(span: &SpanData) -> Value {
+fn span_to_json(span: &SpanData, accept_attributes: &[&str]) -> Value {
     let attributes: HashMap<String, String> =
         span.attributes
             .iter()
             .fold(HashMap::default(), |mut map, (key, value)| {
-                if ACCEPT_ATTRIBUTES.contains(&key.as_str()) {
+                if accept_attributes.contains(&key.as_str()) {
                     map.insert(key.to_string(), value.to_string());
                 }
 
@@ -40,35 +51,96 @@ fn span_to_json(span: &SpanData) -> Value {
         span.name.clone()
     };
 
+    // Serialize the log records attached to the span, keeping their own
+    // timestamp and attributes so intermediate events survive the export.
+    let events: Vec<Value> = span
+        .events
+        .iter()
+        .map(|event| {
+            let event_attributes: HashMap<String, String> = event
+                .attributes
+                .iter()
+                .map(|kv| (kv.key.to_string(), kv.value.to_string()))
+                .collect();
+
+            json!({
+                "name": event.name,
+                "timestamp": event.timestamp.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis().to_string(),
+                "attributes": event_attributes,
+            })
+        })
+        .collect();
+
+    // Capture the status so a failed span surfaces the database error message
+    // rather than just its timing.
+    let status = match &span.status {
+        Status::Ok => json!({ "code": "ok" }),
+        Status::Error { description } => json!({ "code": "error", "description": description }),
+        Status::Unset => json!({ "code": "unset" }),
+    };
+
     json!({
         "span": true,
         "trace_id": span.span_context.trace_id().to_string(),
         "span_id": span.span_context.span_id().to_string(),
         "parent_span_id": span.parent_span_id.to_string(),
         "name": name,
+        "span_kind": format!("{:?}", span.span_kind),
         "start_time": span.start_time.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis().to_string(),
         "end_time": span.end_time.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis().to_string(),
-        "attributes": attributes
+        "attributes": attributes,
+        "events": events,
+        "status": status
     })
 }
 
+/// Back-compat entry point for callers that only have a bare `traceparent`
+/// string: the value is wrapped into a one-entry header map and forwarded to
+/// [`set_span_context_from_headers`], which extracts the full W3C context.
 pub fn set_span_context(span: &Span, trace_id: Option<String>) {
-    if trace_id.is_none() {
-        return;
-    }
+    let headers = trace_id.map(|trace_id| HashMap::from([("traceparent".to_string(), trace_id)]));
+    set_span_context_from_headers(span, headers)
+}
 
-    let mut trace: HashMap<String, String> = HashMap::new();
-    trace.insert("traceparent".to_string(), trace_id.unwrap());
-    let parent_context = opentelemetry::global::get_text_map_propagator(|propagator| propagator.extract(&trace));
+pub fn set_span_context_from_headers(span: &Span, headers: Option<HashMap<String, String>>) {
+    let headers = match headers {
+        Some(headers) if !headers.is_empty() => headers,
+        _ => return,
+    };
+
+    // Extract the full W3C context — `traceparent`, vendor `tracestate` and
+    // OpenTelemetry `baggage` — through the configured propagator, not just the
+    // trace id.
+    let parent_context = opentelemetry::global::get_text_map_propagator(|propagator| propagator.extract(&headers));
 
     span.set_parent(parent_context)
 }
 
-// set the parent context and return the traceparent
+/// Back-compat entry point returning just the `traceparent`, for callers that
+/// forward a single trace id. New callers that need the full propagated context
+/// (vendor `tracestate` and OpenTelemetry `baggage`) should use
+/// [`set_parent_context_from_json_str_propagated`].
 pub fn set_parent_context_from_json_str(span: &Span, trace: String) -> Option<String> {
-    let trace: HashMap<String, String> = serde_json::from_str(&trace).unwrap_or_default();
-    let trace_id = trace.get("traceparent").map(String::from);
-    let cx = opentelemetry::global::get_text_map_propagator(|propagator| propagator.extract(&trace));
-    span.set_parent(cx);
-    trace_id
+    set_parent_context_from_json_str_propagated(span, trace)
+        .and_then(|mut headers| headers.remove("traceparent"))
+}
+
+/// Set the parent context and return the whole re-injected W3C header set
+/// (`traceparent`, `tracestate`, `baggage`), so sampling decisions and baggage
+/// attached upstream survive across the engine boundary.
+pub fn set_parent_context_from_json_str_propagated(span: &Span, trace: String) -> Option<HashMap<String, String>> {
+    let headers: HashMap<String, String> = serde_json::from_str(&trace).unwrap_or_default();
+    if headers.is_empty() {
+        return None;
+    }
+
+    let cx = opentelemetry::global::get_text_map_propagator(|propagator| propagator.extract(&headers));
+    span.set_parent(cx.clone());
+
+    // Re-inject the propagated context so callers receive the full, normalized
+    // header map to forward downstream.
+    let mut propagated: HashMap<String, String> = HashMap::new();
+    opentelemetry::global::get_text_map_propagator(|propagator| propagator.inject_context(&cx, &mut propagated));
+
+    Some(propagated)
 }